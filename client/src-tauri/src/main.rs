@@ -1,71 +1,339 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-use tauri::{Manager, WebviewUrl, WebviewWindowBuilder};
+use std::collections::HashSet;
+use std::sync::Mutex;
+use tauri::{Emitter, Manager, Monitor, WebviewUrl, WebviewWindowBuilder, WindowEvent};
 use serde::{Deserialize, Serialize};
 
+/// Label of the main editor window, used as the target for lifecycle events
+/// emitted about the presentation window.
+const MAIN_WINDOW_LABEL: &str = "main";
+
 #[derive(Debug, Serialize, Deserialize)]
 struct PresentationWindowConfig {
     always_on_top: bool,
+    /// `#rrggbb` or `#rrggbbaa` hex string applied as the window background.
     background_color: String,
     fullscreen: bool,
+    /// Index into `available_monitors()` identifying where the window should
+    /// be placed. `None` falls back to the primary monitor.
+    monitor_index: Option<usize>,
+    /// Label of the window to parent the presentation window to (e.g. the
+    /// main editor window), so it minimizes/raises/closes together and keeps
+    /// correct z-ordering. Falls back to a normal top-level window if the
+    /// label doesn't resolve to an open window.
+    parent: Option<String>,
+    /// Window label, so several independent presentation windows can coexist
+    /// (e.g. one per monitor, or a separate recording feed). Defaults to
+    /// `"presentation"`. Must be non-empty and consist of ASCII alphanumerics
+    /// and [`LABEL_CHARSET`] characters.
+    label: Option<String>,
+}
+
+/// Characters allowed in a presentation window label, beyond alphanumerics.
+const LABEL_CHARSET: &str = "-_/:";
+
+/// Default label used when `PresentationWindowConfig::label` is unset.
+const DEFAULT_PRESENTATION_LABEL: &str = "presentation";
+
+/// Validates a window label against the allowed character set so we fail
+/// with a clear error instead of letting `WebviewWindowBuilder::build` reject
+/// it (or worse, silently mangle it).
+fn validate_label(label: &str) -> Result<(), String> {
+    if !label.is_empty()
+        && label
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || LABEL_CHARSET.contains(c))
+    {
+        Ok(())
+    } else {
+        Err(format!(
+            "invalid window label {label:?}: only alphanumeric characters and '{LABEL_CHARSET}' are allowed"
+        ))
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct MonitorInfo {
+    name: Option<String>,
+    width: u32,
+    height: u32,
+    x: i32,
+    y: i32,
+    scale_factor: f64,
+}
+
+impl From<&Monitor> for MonitorInfo {
+    fn from(monitor: &Monitor) -> Self {
+        Self {
+            name: monitor.name().cloned(),
+            width: monitor.size().width,
+            height: monitor.size().height,
+            x: monitor.position().x,
+            y: monitor.position().y,
+            scale_factor: monitor.scale_factor(),
+        }
+    }
+}
+
+/// Resolves the monitor the presentation window should appear on, falling
+/// back to the primary monitor when no index is given or the index is out
+/// of range.
+fn resolve_monitor(
+    app_handle: &tauri::AppHandle,
+    monitor_index: Option<usize>,
+) -> Result<Option<Monitor>, String> {
+    if let Some(index) = monitor_index {
+        let monitors = app_handle.available_monitors().map_err(|e| e.to_string())?;
+        if let Some(monitor) = monitors.into_iter().nth(index) {
+            return Ok(Some(monitor));
+        }
+    }
+    app_handle.primary_monitor().map_err(|e| e.to_string())
+}
+
+/// Converts a monitor's physical position/size into the logical coordinates
+/// expected by `WebviewWindowBuilder::position`/`inner_size`.
+fn monitor_logical_bounds(monitor: &Monitor) -> ((f64, f64), (f64, f64)) {
+    let scale_factor = monitor.scale_factor();
+    let position = monitor.position().to_logical::<f64>(scale_factor);
+    let size = monitor.size().to_logical::<f64>(scale_factor);
+    ((position.x, position.y), (size.width, size.height))
+}
+
+#[tauri::command]
+fn list_monitors(app_handle: tauri::AppHandle) -> Result<Vec<MonitorInfo>, String> {
+    let monitors = app_handle.available_monitors().map_err(|e| e.to_string())?;
+    Ok(monitors.iter().map(MonitorInfo::from).collect())
+}
+
+/// Key of the presentation window template in the `presentationWindowTemplates`
+/// section of tauri.conf.json.
+const PRESENTATION_TEMPLATE_LABEL: &str = "presentation";
+
+/// Raw tauri.conf.json contents, so the `presentationWindowTemplates` section
+/// can be read as plain data without Tauri's window bootstrap treating it as
+/// a window to auto-instantiate at startup (unlike `app.windows`, every entry
+/// of which is built and loaded on launch).
+const RAW_TAURI_CONFIG: &str = include_str!("../tauri.conf.json");
+
+/// Looks up a window template by key from tauri.conf.json's
+/// `presentationWindowTemplates` section.
+fn named_window_config(label: &str) -> Option<tauri::utils::config::WindowConfig> {
+    let root: serde_json::Value = serde_json::from_str(RAW_TAURI_CONFIG).ok()?;
+    let template = root.get("presentationWindowTemplates")?.get(label)?;
+    serde_json::from_value(template.clone()).ok()
+}
+
+/// Parses a `#rrggbb` or `#rrggbbaa` hex string into a `tauri::Color`.
+fn parse_hex_color(hex: &str) -> Result<tauri::window::Color, String> {
+    let hex = hex.trim_start_matches('#');
+    if !hex.is_ascii() {
+        return Err(format!("invalid background_color: {hex}"));
+    }
+    let channel = |i: usize| -> Result<u8, String> {
+        u8::from_str_radix(&hex[i..i + 2], 16).map_err(|e| e.to_string())
+    };
+    match hex.len() {
+        6 => Ok(tauri::window::Color(channel(0)?, channel(2)?, channel(4)?, 255)),
+        8 => Ok(tauri::window::Color(channel(0)?, channel(2)?, channel(4)?, channel(6)?)),
+        _ => Err(format!("invalid background_color: {hex}")),
+    }
+}
+
+/// Presenter viewport (pan/zoom) mirrored to the audience view.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct ViewportTransform {
+    x: f64,
+    y: f64,
+    scale: f64,
+}
+
+/// Authoritative presenter state, mirrored to every open presentation window.
+///
+/// `revision` increments on every push so a (re)opened presentation window
+/// can tell whether the state it rendered last is stale.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct PresentationState {
+    page_index: u32,
+    viewport: ViewportTransform,
+    revision: u64,
+}
+
+/// Pushes a new presenter state and mirrors it to every open presentation
+/// window.
+///
+/// This is the single authoritative sync channel between editor and
+/// presentation windows: the editor calls this on every page or viewport
+/// change, and the presentation windows apply the resulting event instead of
+/// polling. Only the page index and viewport are carried; laser pointer and
+/// stroke data are not part of this state.
+#[tauri::command]
+fn push_presentation_state(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<Mutex<PresentationState>>,
+    registry: tauri::State<PresentationWindowRegistry>,
+    page_index: u32,
+    viewport: ViewportTransform,
+) -> Result<PresentationState, String> {
+    let snapshot = {
+        let mut guard = state.lock().map_err(|e| e.to_string())?;
+        guard.page_index = page_index;
+        guard.viewport = viewport;
+        guard.revision += 1;
+        guard.clone()
+    };
+
+    for label in open_presentation_labels(&app_handle, &registry)? {
+        app_handle
+            .emit_to(&label, "presentation-state", &snapshot)
+            .map_err(|e| e.to_string())?;
+    }
+
+    Ok(snapshot)
+}
+
+/// Returns the current presenter state, so a (re)opened presentation window
+/// can recover it without waiting for the next push.
+#[tauri::command]
+fn get_presentation_state(
+    state: tauri::State<Mutex<PresentationState>>,
+) -> Result<PresentationState, String> {
+    state.lock().map(|guard| guard.clone()).map_err(|e| e.to_string())
+}
+
+/// Labels of presentation windows built by `open_presentation_window`,
+/// removed again by the window's `Destroyed` handler, used only to tell
+/// presentation windows apart from other windows (e.g. `"main"`) when
+/// filtering `AppHandle::webview_windows()`. Membership here does not by
+/// itself mean the window is still open — `open_presentation_labels` below
+/// is the source of truth for that, so a missed removal (e.g. a teardown
+/// path that skips the `Destroyed` handler) can't cause a stale label to be
+/// reported as open or addressed by `emit_to`.
+type PresentationWindowRegistry = Mutex<HashSet<String>>;
+
+/// Labels of the presentation windows that are actually still open, i.e. the
+/// intersection of `registry` with `app_handle.webview_windows()`.
+fn open_presentation_labels(
+    app_handle: &tauri::AppHandle,
+    registry: &PresentationWindowRegistry,
+) -> Result<Vec<String>, String> {
+    let known = registry.lock().map_err(|e| e.to_string())?;
+    let live = app_handle.webview_windows();
+    Ok(known.iter().filter(|label| live.contains_key(*label)).cloned().collect())
+}
+
+#[tauri::command]
+fn list_presentation_windows(
+    app_handle: tauri::AppHandle,
+    registry: tauri::State<PresentationWindowRegistry>,
+) -> Result<Vec<String>, String> {
+    open_presentation_labels(&app_handle, &registry)
 }
 
 #[tauri::command]
 async fn open_presentation_window(
     app_handle: tauri::AppHandle,
+    registry: tauri::State<PresentationWindowRegistry>,
     config: PresentationWindowConfig,
 ) -> Result<(), String> {
-    // Check if presentation window already exists
-    if let Some(_window) = app_handle.get_webview_window("presentation") {
+    let label = config
+        .label
+        .clone()
+        .unwrap_or_else(|| DEFAULT_PRESENTATION_LABEL.to_string());
+    validate_label(&label)?;
+
+    // Check if a presentation window with this label already exists
+    if let Some(_window) = app_handle.get_webview_window(&label) {
         return Err("Presentation window already open".to_string());
     }
 
-    // Create presentation window URL with query parameter
-    let url = WebviewUrl::App("index.html?mode=presentation".into());
-
-    // Build the presentation window
-    // In debug mode, use decorations to make it easier to debug
-    #[cfg(debug_assertions)]
-    let window = WebviewWindowBuilder::new(
-        &app_handle,
-        "presentation",
-        url,
-    )
-    .title("Whiteboard - Presentation")
-    .inner_size(1920.0, 1080.0)
-    .resizable(true)
-    .decorations(true) // Show title bar in debug mode
-    .always_on_top(config.always_on_top)
-    .fullscreen(config.fullscreen)
-    .build()
-    .map_err(|e| e.to_string())?;
-
-    // In release mode, use frameless window
-    #[cfg(not(debug_assertions))]
-    let window = WebviewWindowBuilder::new(
-        &app_handle,
-        "presentation",
-        url,
-    )
-    .title("Whiteboard - Presentation")
-    .inner_size(1920.0, 1080.0)
-    .resizable(true)
-    .decorations(false) // Frameless window in release
-    .always_on_top(config.always_on_top)
-    .fullscreen(config.fullscreen)
-    .build()
-    .map_err(|e| e.to_string())?;
+    // Resolve where the window should live before building it, so the
+    // initial placement lands on the requested monitor instead of the OS
+    // default.
+    let monitor = resolve_monitor(&app_handle, config.monitor_index)?;
+
+    // Build the presentation window from the `presentationWindowTemplates`
+    // entry in tauri.conf.json when one is defined, so chrome (size,
+    // decorations, resizable) is tunable without recompiling. Falls back to
+    // the historical hard-coded defaults otherwise.
+    let mut builder = if let Some(mut window_config) = named_window_config(PRESENTATION_TEMPLATE_LABEL) {
+        window_config.label = label.clone();
+        WebviewWindowBuilder::from_config(&app_handle, &window_config).map_err(|e| e.to_string())?
+    } else {
+        let url = WebviewUrl::App("index.html?mode=presentation".into());
+        WebviewWindowBuilder::new(&app_handle, &label, url)
+            .title("Whiteboard - Presentation")
+            .inner_size(1920.0, 1080.0)
+            .resizable(true)
+            .decorations(cfg!(debug_assertions))
+    };
+
+    // Runtime overrides on top of the template/defaults. Force visibility
+    // explicitly: a template author may set `visible: false` to stage a
+    // window before it's ready, and this command should always end up
+    // showing the window it just built.
+    builder = builder
+        .visible(true)
+        .always_on_top(config.always_on_top)
+        .fullscreen(config.fullscreen)
+        .background_color(parse_hex_color(&config.background_color)?);
+
+    // No monitor resolved (e.g. `primary_monitor()` reports none, which
+    // happens on some Wayland setups): leave the template/default builder's
+    // own size and position alone instead of clobbering it.
+    if let Some(monitor) = &monitor {
+        let (position, size) = monitor_logical_bounds(monitor);
+        builder = builder.position(position.0, position.1).inner_size(size.0, size.1);
+    }
+
+    // Parent to the editor window when requested so it minimizes, raises and
+    // closes together with it. Missing parent just means a normal window.
+    if let Some(parent_label) = &config.parent {
+        if let Some(parent_window) = app_handle.get_webview_window(parent_label) {
+            builder = builder.parent(&parent_window).map_err(|e| e.to_string())?;
+        }
+    }
+
+    let window = builder.build().map_err(|e| e.to_string())?;
+
+    registry.lock().map_err(|e| e.to_string())?.insert(label.clone());
 
     // Dev tools available but not auto-opened (use right-click -> Inspect)
-    let _ = window;
+
+    // Let the editor UI drive button state from events instead of polling:
+    // tell it the window closed even if the OS (or a crash) closed it rather
+    // than a call to `close_presentation_window`. Only `Destroyed` fires
+    // exactly once the window is actually gone — `CloseRequested` also fires
+    // on an ordinary user-initiated close and would double-emit this event.
+    let closed_handle = app_handle.clone();
+    let closed_label = label.clone();
+    window.on_window_event(move |event| {
+        if matches!(event, WindowEvent::Destroyed) {
+            if let Some(registry) = closed_handle.try_state::<PresentationWindowRegistry>() {
+                if let Ok(mut labels) = registry.lock() {
+                    labels.remove(&closed_label);
+                }
+            }
+            let _ = closed_handle.emit_to(MAIN_WINDOW_LABEL, "presentation-window-closed", &closed_label);
+        }
+    });
+
+    app_handle
+        .emit_to(MAIN_WINDOW_LABEL, "presentation-window-opened", &label)
+        .map_err(|e| e.to_string())?;
 
     Ok(())
 }
 
 #[tauri::command]
-async fn close_presentation_window(app_handle: tauri::AppHandle) -> Result<(), String> {
-    if let Some(window) = app_handle.get_webview_window("presentation") {
+async fn close_presentation_window(
+    app_handle: tauri::AppHandle,
+    label: Option<String>,
+) -> Result<(), String> {
+    let label = label.unwrap_or_else(|| DEFAULT_PRESENTATION_LABEL.to_string());
+    if let Some(window) = app_handle.get_webview_window(&label) {
         window.close().map_err(|e| e.to_string())?;
         Ok(())
     } else {
@@ -78,11 +346,29 @@ async fn update_presentation_window(
     app_handle: tauri::AppHandle,
     config: PresentationWindowConfig,
 ) -> Result<(), String> {
-    if let Some(window) = app_handle.get_webview_window("presentation") {
+    let label = config
+        .label
+        .clone()
+        .unwrap_or_else(|| DEFAULT_PRESENTATION_LABEL.to_string());
+    if let Some(window) = app_handle.get_webview_window(&label) {
         window.set_always_on_top(config.always_on_top)
             .map_err(|e| e.to_string())?;
         window.set_fullscreen(config.fullscreen)
             .map_err(|e| e.to_string())?;
+
+        // Allow moving the window to a different monitor on the fly.
+        if config.monitor_index.is_some() {
+            if let Some(monitor) = resolve_monitor(&app_handle, config.monitor_index)? {
+                let (position, size) = monitor_logical_bounds(&monitor);
+                window
+                    .set_position(tauri::LogicalPosition::new(position.0, position.1))
+                    .map_err(|e| e.to_string())?;
+                window
+                    .set_size(tauri::LogicalSize::new(size.0, size.1))
+                    .map_err(|e| e.to_string())?;
+            }
+        }
+
         Ok(())
     } else {
         Err("Presentation window not found".to_string())
@@ -90,19 +376,79 @@ async fn update_presentation_window(
 }
 
 #[tauri::command]
-fn is_presentation_window_open(app_handle: tauri::AppHandle) -> bool {
-    app_handle.get_webview_window("presentation").is_some()
+fn is_presentation_window_open(app_handle: tauri::AppHandle, label: Option<String>) -> bool {
+    let label = label.unwrap_or_else(|| DEFAULT_PRESENTATION_LABEL.to_string());
+    app_handle.get_webview_window(&label).is_some()
 }
 
 fn main() {
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
+        .manage(Mutex::new(PresentationState::default()))
+        .manage(PresentationWindowRegistry::default())
         .invoke_handler(tauri::generate_handler![
             open_presentation_window,
             close_presentation_window,
             update_presentation_window,
-            is_presentation_window_open
+            is_presentation_window_open,
+            list_monitors,
+            push_presentation_state,
+            get_presentation_state,
+            list_presentation_windows
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_label_accepts_allowed_characters() {
+        assert!(validate_label("presentation").is_ok());
+        assert!(validate_label("Presentation-2").is_ok());
+        assert!(validate_label("audience_feed/2:recording").is_ok());
+    }
+
+    #[test]
+    fn validate_label_rejects_empty() {
+        assert!(validate_label("").is_err());
+    }
+
+    #[test]
+    fn validate_label_rejects_forbidden_characters() {
+        assert!(validate_label("presentation window").is_err());
+        assert!(validate_label("presentation.html").is_err());
+        assert!(validate_label("../etc").is_err());
+    }
+
+    #[test]
+    fn parse_hex_color_accepts_rgb_and_rgba() {
+        assert_eq!(
+            parse_hex_color("#000000").unwrap(),
+            tauri::window::Color(0, 0, 0, 255)
+        );
+        assert_eq!(
+            parse_hex_color("ffffff").unwrap(),
+            tauri::window::Color(255, 255, 255, 255)
+        );
+        assert_eq!(
+            parse_hex_color("#11223344").unwrap(),
+            tauri::window::Color(0x11, 0x22, 0x33, 0x44)
+        );
+    }
+
+    #[test]
+    fn parse_hex_color_rejects_wrong_length() {
+        assert!(parse_hex_color("#").is_err());
+        assert!(parse_hex_color("#abcde").is_err());
+        assert!(parse_hex_color("#abcdefa").is_err());
+        assert!(parse_hex_color("#abcdefabc").is_err());
+    }
+
+    #[test]
+    fn parse_hex_color_rejects_non_ascii_without_panicking() {
+        assert!(parse_hex_color("abc€").is_err());
+    }
+}